@@ -14,6 +14,9 @@ pub struct Config {
     pub cache_dir: PathBuf,
     pub tag: String,
     pub secctx: Option<String>,
+    /// Optional label attached to every log line, to tell daemons apart
+    /// once their logs are aggregated off-host.
+    pub instance_name: Option<String>,
     #[serde(default)]
     pub limits: Limits,
     #[serde(default)]
@@ -130,6 +133,13 @@ impl Config {
         let l = &self.limits;
         crate::proto::cmd::validate_limit_triplet("b", l.bstop, l.bcull, l.brun)?;
         crate::proto::cmd::validate_limit_triplet("f", l.fstop, l.fcull, l.frun)?;
+        if let Some(name) = &self.instance_name {
+            if name.is_empty() || name.contains(|c: char| c.is_whitespace() || c.is_control()) {
+                return Err(Error::config(
+                    "instance_name must be non-empty and free of whitespace and control characters",
+                ));
+            }
+        }
         if self.cull.batch_size == 0 {
             return Err(Error::config("cull.batch_size must be > 0"));
         }
@@ -218,6 +228,7 @@ mod tests {
             cache_dir: PathBuf::from("/var/cache/fscache"),
             tag: "nfscache".into(),
             secctx: None,
+            instance_name: None,
             limits: Limits::default(),
             cull: Cull::default(),
             log: Log::default(),
@@ -235,6 +246,25 @@ mod tests {
         assert!(cfg.validate().is_err());
     }
 
+    #[test]
+    fn validates_instance_name() {
+        let s = r#"
+            cache_dir = "/var/cache/fscache"
+            tag = "nfscache"
+            instance_name = "gpu-node-07"
+        "#;
+        let mut cfg: Config = toml::from_str(s).unwrap();
+        assert_eq!(cfg.instance_name.as_deref(), Some("gpu-node-07"));
+        cfg.validate().unwrap();
+
+        cfg.instance_name = Some("gpu node".into());
+        assert!(cfg.validate().is_err());
+        cfg.instance_name = Some(String::new());
+        assert!(cfg.validate().is_err());
+        cfg.instance_name = Some("gpu\x1bnode".into());
+        assert!(cfg.validate().is_err());
+    }
+
     #[test]
     fn rejects_unknown_fields() {
         let s = r#"
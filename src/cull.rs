@@ -49,6 +49,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use tracing::{debug, info, warn};
 
 use crate::proto::{cmd, Device};
+use crate::systemd_notify::Watchdog;
 
 /// Configurable knobs for one cull pass.
 #[derive(Debug, Clone)]
@@ -65,9 +66,29 @@ pub struct CullStats {
     pub skipped_busy: usize,
     pub skipped_changed: usize,
     pub errored: usize,
+    /// Entries the walk could not read or stat (EACCES, EIO, ...). Entries
+    /// that vanish mid-walk are not counted; the kernel culls concurrently.
+    pub walk_errors: usize,
     pub graveyard_removed: usize,
 }
 
+/// What the next cull pass would pick, as reported by [`preview`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CullPreview {
+    /// Oldest first.
+    pub victims: Vec<CullVictim>,
+    /// Entries the walk could not read; non-zero means `victims` may be
+    /// missing objects older than the ones listed.
+    pub walk_errors: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CullVictim {
+    pub path: PathBuf,
+    pub size: u64,
+    pub atime_secs: i64,
+}
+
 impl CullStats {
     pub fn made_progress(self) -> bool {
         self.culled > 0 || self.graveyard_removed > 0
@@ -109,9 +130,14 @@ impl Candidate {
 /// but never propagated: the kernel re-signals if more culling is needed,
 /// and a single bad object should not bring the daemon down.
 pub fn run_pass(dev: &Device, ctx: &CullCtx, stop: &AtomicBool) -> CullStats {
+    run_pass_with(dev, ctx, WalkHooks::new(Some(stop), None))
+}
+
+/// [`run_pass`] with the daemon's watchdog fed throughout the pass.
+pub(crate) fn run_pass_with(dev: &Device, ctx: &CullCtx, hooks: WalkHooks<'_>) -> CullStats {
     let started = std::time::Instant::now();
-    let mut stats = clean_graveyard_interruptible(&ctx.cache_root, Some(stop));
-    if stop.load(Ordering::Relaxed) {
+    let mut stats = clean_graveyard_interruptible(&ctx.cache_root, hooks);
+    if hooks.should_stop() {
         return stats;
     }
     let cache_subdir = ctx.cache_root.join("cache");
@@ -123,7 +149,9 @@ pub fn run_pass(dev: &Device, ctx: &CullCtx, stop: &AtomicBool) -> CullStats {
         return stats;
     }
 
-    let oldest = collect_oldest_interruptible(&cache_subdir, ctx.batch_size, Some(stop));
+    let Walk { oldest, errors } =
+        collect_oldest_interruptible(&cache_subdir, ctx.batch_size, hooks);
+    stats.walk_errors = errors;
     stats.candidates = oldest.len();
     debug!(found = stats.candidates, "cull candidates");
     if oldest.is_empty() {
@@ -133,7 +161,7 @@ pub fn run_pass(dev: &Device, ctx: &CullCtx, stop: &AtomicBool) -> CullStats {
     let saved_cwd = std::env::current_dir().ok();
 
     for cand in oldest {
-        if stop.load(Ordering::Relaxed) {
+        if hooks.should_stop() {
             break;
         }
         match cand.atime_changed() {
@@ -184,27 +212,59 @@ pub fn run_pass(dev: &Device, ctx: &CullCtx, stop: &AtomicBool) -> CullStats {
         skipped_busy = stats.skipped_busy,
         skipped_changed = stats.skipped_changed,
         errored = stats.errored,
+        walk_errors = stats.walk_errors,
         graveyard_removed = stats.graveyard_removed,
         "cull pass done"
     );
     stats
 }
 
+/// List the objects a cull pass would send to the kernel, oldest first,
+/// without culling anything. Does not touch `/dev/cachefiles`, so it is
+/// safe to run alongside a live daemon. Objects found busy or re-read
+/// by the time a real pass runs would be skipped there.
+pub fn preview(ctx: &CullCtx) -> CullPreview {
+    let walk = collect_oldest_interruptible(
+        &ctx.cache_root.join("cache"),
+        ctx.batch_size,
+        WalkHooks::default(),
+    );
+    CullPreview {
+        victims: walk
+            .oldest
+            .into_iter()
+            .map(|c| CullVictim {
+                path: c.path(),
+                size: c.size,
+                atime_secs: c.atime_secs,
+            })
+            .collect(),
+        walk_errors: walk.errors,
+    }
+}
+
+/// Outcome of one bounded walk over the cache subtree.
+struct Walk {
+    /// Ascending atime, at most `k` entries.
+    oldest: Vec<Candidate>,
+    errors: usize,
+}
+
 /// Walk the cache subtree and return the `k` oldest-by-atime objects in
 /// ascending-atime order. Uses a max-heap of size k → O(N log k) time,
 /// O(k) memory regardless of cache size.
 #[cfg(test)]
 fn collect_oldest(root: &Path, k: usize) -> Vec<Candidate> {
-    collect_oldest_interruptible(root, k, None)
+    collect_oldest_interruptible(root, k, WalkHooks::default()).oldest
 }
 
-fn collect_oldest_interruptible(
-    root: &Path,
-    k: usize,
-    stop: Option<&AtomicBool>,
-) -> Vec<Candidate> {
+fn collect_oldest_interruptible(root: &Path, k: usize, hooks: WalkHooks<'_>) -> Walk {
+    let mut errors = 0;
     if k == 0 {
-        return Vec::new();
+        return Walk {
+            oldest: Vec::new(),
+            errors,
+        };
     }
     let mut heap: BinaryHeap<Candidate> = BinaryHeap::with_capacity(k);
 
@@ -221,10 +281,19 @@ fn collect_oldest_interruptible(
         .into_iter();
 
     for entry in entries {
-        if stop_requested(stop) {
+        if hooks.should_stop() {
             break;
         }
-        let Ok(entry) = entry else { continue };
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                if walk_error_counts(e.io_error()) {
+                    debug!(path = ?e.path(), error = %e, "cull walk: unreadable entry");
+                    errors += 1;
+                }
+                continue;
+            }
+        };
         let file_type = entry.file_type();
         if !(file_type.is_file() || file_type.is_dir()) {
             continue;
@@ -238,7 +307,16 @@ fn collect_oldest_interruptible(
         let Some(parent) = entry.path().parent() else {
             continue;
         };
-        let Ok(meta) = entry.metadata() else { continue };
+        let meta = match entry.metadata() {
+            Ok(meta) => meta,
+            Err(e) => {
+                if walk_error_counts(e.io_error()) {
+                    debug!(path = %entry.path().display(), error = %e, "cull walk: stat failed");
+                    errors += 1;
+                }
+                continue;
+            }
+        };
         let cand = Candidate {
             atime_secs: meta.atime(),
             atime_nsecs: meta.atime_nsec(),
@@ -259,11 +337,43 @@ fn collect_oldest_interruptible(
     }
 
     // Sorts ascending by Ord (atime_secs first) → oldest first.
-    heap.into_sorted_vec()
+    Walk {
+        oldest: heap.into_sorted_vec(),
+        errors,
+    }
+}
+
+/// Entries that disappear between readdir and stat are routine — the
+/// kernel culls and invalidates objects concurrently — so only other
+/// errors count as walk errors.
+fn walk_error_counts(io: Option<&std::io::Error>) -> bool {
+    io.map_or(true, |e| e.kind() != std::io::ErrorKind::NotFound)
 }
 
-fn stop_requested(stop: Option<&AtomicBool>) -> bool {
-    stop.is_some_and(|stop| stop.load(Ordering::Relaxed))
+/// Checked once per entry by the long-running loops in this module. Stops
+/// early when `stop` is set, and keeps the systemd watchdog fed so a slow
+/// walk over a large cache is not mistaken for a wedged daemon.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct WalkHooks<'a> {
+    stop: Option<&'a AtomicBool>,
+    watchdog: Option<&'a Watchdog>,
+}
+
+impl<'a> WalkHooks<'a> {
+    pub(crate) fn new(stop: Option<&'a AtomicBool>, watchdog: Option<&'a Watchdog>) -> Self {
+        Self { stop, watchdog }
+    }
+
+    fn keepalive(&self) {
+        if let Some(watchdog) = self.watchdog {
+            watchdog.ping_if_due();
+        }
+    }
+
+    fn should_stop(&self) -> bool {
+        self.keepalive();
+        self.stop.is_some_and(|stop| stop.load(Ordering::Relaxed))
+    }
 }
 
 fn is_cache_object_name(name: &str) -> bool {
@@ -274,10 +384,10 @@ fn is_cache_object_name(name: &str) -> bool {
 }
 
 pub fn clean_graveyard(cache_root: &Path) -> CullStats {
-    clean_graveyard_interruptible(cache_root, None)
+    clean_graveyard_interruptible(cache_root, WalkHooks::default())
 }
 
-fn clean_graveyard_interruptible(cache_root: &Path, stop: Option<&AtomicBool>) -> CullStats {
+pub(crate) fn clean_graveyard_interruptible(cache_root: &Path, hooks: WalkHooks<'_>) -> CullStats {
     let mut stats = CullStats::default();
     let graveyard = cache_root.join("graveyard");
     let Ok(entries) = std::fs::read_dir(&graveyard) else {
@@ -285,7 +395,7 @@ fn clean_graveyard_interruptible(cache_root: &Path, stop: Option<&AtomicBool>) -
     };
 
     for entry in entries {
-        if stop_requested(stop) {
+        if hooks.should_stop() {
             break;
         }
         let Ok(entry) = entry else {
@@ -294,7 +404,7 @@ fn clean_graveyard_interruptible(cache_root: &Path, stop: Option<&AtomicBool>) -
         };
         let path = entry.path();
         let remove_result = match entry.file_type() {
-            Ok(ft) if ft.is_dir() => std::fs::remove_dir_all(&path),
+            Ok(ft) if ft.is_dir() => remove_tree(&path, hooks),
             Ok(_) => std::fs::remove_file(&path),
             Err(e) => Err(e),
         };
@@ -309,6 +419,27 @@ fn clean_graveyard_interruptible(cache_root: &Path, stop: Option<&AtomicBool>) -
     stats
 }
 
+/// `remove_dir_all` that feeds the watchdog per entry. A graveyard entry
+/// can be a whole buried volume holding millions of objects, so a single
+/// removal may outlast the watchdog timeout on its own. The stop flag is
+/// only honoured between graveyard entries; a half-removed tree would just
+/// be finished on the next cleanup anyway.
+fn remove_tree(path: &Path, hooks: WalkHooks<'_>) -> std::io::Result<()> {
+    let entries = walkdir::WalkDir::new(path)
+        .follow_links(false)
+        .contents_first(true);
+    for entry in entries {
+        hooks.keepalive();
+        let entry = entry?;
+        if entry.file_type().is_dir() {
+            std::fs::remove_dir(entry.path())?;
+        } else {
+            std::fs::remove_file(entry.path())?;
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -404,11 +535,42 @@ mod tests {
         assert_eq!(names, vec!["Iindex", "Scookie"]);
     }
 
+    #[test]
+    fn preview_lists_oldest_cookies_under_cache_subdir() {
+        let dir = tempdir();
+        let bucket = cookie_bucket(&dir.join("cache"));
+        touch(&bucket.join("Snew"), 20);
+        touch(&bucket.join("Sold"), 10);
+
+        let ctx = CullCtx {
+            cache_root: dir.clone(),
+            batch_size: 1,
+        };
+        let preview = preview(&ctx);
+        assert_eq!(preview.walk_errors, 0);
+        assert_eq!(preview.victims.len(), 1);
+        assert_eq!(preview.victims[0].path, bucket.join("Sold"));
+        assert_eq!(preview.victims[0].size, 1);
+        assert_eq!(preview.victims[0].atime_secs, 1_700_000_010);
+    }
+
+    #[test]
+    fn walk_errors_ignore_entries_that_vanished() {
+        use std::io::{Error, ErrorKind};
+        assert!(!walk_error_counts(Some(&Error::from(ErrorKind::NotFound))));
+        assert!(walk_error_counts(Some(&Error::from(
+            ErrorKind::PermissionDenied
+        ))));
+        // walkdir reports loops without an io::Error.
+        assert!(walk_error_counts(None));
+    }
+
     #[test]
     fn graveyard_cleanup_removes_entries() {
         let dir = tempdir();
         let graveyard = dir.join("graveyard");
-        fs::create_dir_all(graveyard.join("dead-dir")).unwrap();
+        fs::create_dir_all(graveyard.join("dead-dir").join("@00")).unwrap();
+        fs::write(graveyard.join("dead-dir").join("@00").join("Scookie"), b"x").unwrap();
         fs::write(graveyard.join("dead-file"), b"x").unwrap();
 
         let stats = clean_graveyard(&dir);
@@ -1,19 +1,31 @@
 //! Main event loop. Polls `/dev/cachefiles`; on POLLIN/POLLOUT, reads the
-//! kernel's state line and triggers a cull pass when needed.
+//! kernel's state line and triggers a cull pass when needed. When the unit
+//! sets `WatchdogSec=`, the loop and the cull / graveyard walks (per entry)
+//! feed the systemd watchdog, so only a genuinely wedged daemon gets
+//! restarted — a slow walk over a large cache does not.
 
+use std::collections::VecDeque;
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 
 use tracing::{debug, error, info, warn};
 
-use crate::cull::{self, CullCtx};
+use crate::cull::{self, CullCtx, WalkHooks};
 use crate::error::{Error, Result};
 use crate::proto::{CacheState, ConfigCmd, Device};
 
 /// How often to log a heartbeat / metrics summary at INFO when idle.
 const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(60);
 
+/// Longest window of the windowed cull totals (1 / 5 / 15 minutes).
+const CULL_WINDOW_MAX: Duration = Duration::from_secs(15 * 60);
+
+/// Passes finishing this close together share one [`CullWindows`] record,
+/// which bounds the history to `CULL_WINDOW_MAX / CULL_WINDOW_GRANULARITY`
+/// entries however often the kernel asks for culling.
+const CULL_WINDOW_GRANULARITY: Duration = Duration::from_secs(1);
+
 /// How often to drain cachefiles' graveyard even when the kernel is not asking
 /// for active culling.
 const GRAVEYARD_INTERVAL: Duration = Duration::from_secs(30);
@@ -56,11 +68,29 @@ impl<'a> Daemon<'a> {
             }
         }
 
+        // Never block in poll() for longer than the keep-alive interval.
+        let watchdog = crate::systemd_notify::Watchdog::from_env();
+        let poll_timeout_ms = watchdog.as_ref().map_or(POLL_TIMEOUT_MS, |w| {
+            let ms = w.interval().as_millis().try_into().unwrap_or(i32::MAX);
+            POLL_TIMEOUT_MS.min(ms.max(1))
+        });
+        if let Some(w) = &watchdog {
+            debug!(
+                interval_ms = w.interval().as_millis() as u64,
+                "systemd watchdog enabled"
+            );
+        }
+        let hooks = WalkHooks::new(Some(self.stop), watchdog.as_ref());
+
         let mut buf = [0u8; 256];
         let mut last_heartbeat = Instant::now();
+        let mut cull_windows = CullWindows::default();
         let mut last_graveyard = Instant::now();
         let mut last_state: Option<CacheState> = None;
-        log_graveyard_cleanup(cull::clean_graveyard(&self.cull.cache_root));
+        log_graveyard_cleanup(cull::clean_graveyard_interruptible(
+            &self.cull.cache_root,
+            hooks,
+        ));
 
         while !self.stop.load(Ordering::Relaxed) {
             let mut pollfd = libc::pollfd {
@@ -68,7 +98,7 @@ impl<'a> Daemon<'a> {
                 events: libc::POLLIN | libc::POLLOUT,
                 revents: 0,
             };
-            let r = unsafe { libc::poll(&mut pollfd, 1, POLL_TIMEOUT_MS) };
+            let r = unsafe { libc::poll(&mut pollfd, 1, poll_timeout_ms) };
             if r < 0 {
                 let err = std::io::Error::last_os_error();
                 if err.raw_os_error() == Some(libc::EINTR) {
@@ -91,7 +121,8 @@ impl<'a> Daemon<'a> {
                         Ok(state) => {
                             debug!(?state, "state");
                             if state.culling {
-                                let stats = cull::run_pass(&self.dev, &self.cull, self.stop);
+                                let stats = cull::run_pass_with(&self.dev, &self.cull, hooks);
+                                cull_windows.record(Instant::now(), stats);
                                 if !stats.made_progress() {
                                     warn!(
                                         candidates = stats.candidates,
@@ -114,13 +145,24 @@ impl<'a> Daemon<'a> {
                 }
             }
 
+            if let Some(w) = &watchdog {
+                w.ping_if_due();
+            }
+
             if last_graveyard.elapsed() >= GRAVEYARD_INTERVAL {
                 last_graveyard = Instant::now();
-                log_graveyard_cleanup(cull::clean_graveyard(&self.cull.cache_root));
+                log_graveyard_cleanup(cull::clean_graveyard_interruptible(
+                    &self.cull.cache_root,
+                    hooks,
+                ));
             }
 
             if last_heartbeat.elapsed() >= HEARTBEAT_INTERVAL {
                 last_heartbeat = Instant::now();
+                let minutes = |m: u64| Duration::from_secs(m * 60);
+                let (culled_1m, bytes_freed_1m) = cull_windows.total(minutes(1), last_heartbeat);
+                let (culled_5m, bytes_freed_5m) = cull_windows.total(minutes(5), last_heartbeat);
+                let (culled_15m, bytes_freed_15m) = cull_windows.total(minutes(15), last_heartbeat);
                 if let Some(s) = last_state {
                     info!(
                         culling = s.culling,
@@ -130,10 +172,24 @@ impl<'a> Daemon<'a> {
                         fstop = s.fstop,
                         fcull = s.fcull,
                         frun = s.frun,
+                        culled_1m,
+                        culled_5m,
+                        culled_15m,
+                        bytes_freed_1m,
+                        bytes_freed_5m,
+                        bytes_freed_15m,
                         "heartbeat"
                     );
                 } else {
-                    info!("heartbeat (no state read yet)");
+                    info!(
+                        culled_1m,
+                        culled_5m,
+                        culled_15m,
+                        bytes_freed_1m,
+                        bytes_freed_5m,
+                        bytes_freed_15m,
+                        "heartbeat (no state read yet)"
+                    );
                 }
             }
         }
@@ -144,6 +200,51 @@ impl<'a> Daemon<'a> {
     }
 }
 
+/// Objects and bytes culled over the last 1 / 5 / 15 minutes, in the
+/// spirit of the load average. Each pass is stamped with when it finished,
+/// so windows cover wall-clock time even when a slow pass delays the
+/// heartbeat.
+#[derive(Debug, Default)]
+struct CullWindows {
+    // `(finished, objects, bytes)`, oldest first.
+    passes: VecDeque<(Instant, usize, u64)>,
+}
+
+impl CullWindows {
+    fn record(&mut self, now: Instant, stats: cull::CullStats) {
+        while let Some(&(at, _, _)) = self.passes.front() {
+            if now.saturating_duration_since(at) <= CULL_WINDOW_MAX {
+                break;
+            }
+            self.passes.pop_front();
+        }
+        if stats.culled == 0 && stats.bytes_freed == 0 {
+            return;
+        }
+        match self.passes.back_mut() {
+            Some((at, objects, bytes))
+                if now.saturating_duration_since(*at) < CULL_WINDOW_GRANULARITY =>
+            {
+                *objects += stats.culled;
+                *bytes += stats.bytes_freed;
+            }
+            _ => self
+                .passes
+                .push_back((now, stats.culled, stats.bytes_freed)),
+        }
+    }
+
+    /// `(objects, bytes)` culled by passes that finished within `window`
+    /// of `now`.
+    fn total(&self, window: Duration, now: Instant) -> (usize, u64) {
+        self.passes
+            .iter()
+            .rev()
+            .take_while(|&&(at, _, _)| now.saturating_duration_since(at) <= window)
+            .fold((0, 0), |(o, b), &(_, po, pb)| (o + po, b + pb))
+    }
+}
+
 fn log_graveyard_cleanup(stats: cull::CullStats) {
     if stats.graveyard_removed > 0 || stats.errored > 0 {
         info!(
@@ -249,4 +350,37 @@ mod tests {
     // observational. The procfs paths they read are not portable to
     // CI sandboxes either; we cover the broader behavior in the e2e
     // script and rely on integration testing on the test machine.
+
+    use super::*;
+
+    fn culled(n: usize) -> cull::CullStats {
+        cull::CullStats {
+            culled: n,
+            bytes_freed: n as u64 * 10,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn cull_windows_sum_by_age() {
+        let t0 = Instant::now();
+        let at = |secs: u64| t0 + Duration::from_secs(secs);
+        let mut w = CullWindows::default();
+        w.record(at(0), culled(1));
+        w.record(at(0), culled(2));
+        w.record(at(0), culled(0));
+        assert_eq!(w.passes.len(), 1, "same-second passes share a record");
+        assert_eq!(w.total(Duration::from_secs(60), at(30)), (3, 30));
+
+        // A heartbeat delayed by a 4-minute pass still sees only what
+        // finished in the last minute.
+        w.record(at(250), culled(4));
+        assert_eq!(w.total(Duration::from_secs(60), at(270)), (4, 40));
+        assert_eq!(w.total(Duration::from_secs(300), at(270)), (7, 70));
+
+        // Records older than the longest window are dropped.
+        w.record(at(1000), culled(5));
+        assert_eq!(w.passes.len(), 2);
+        assert_eq!(w.total(CULL_WINDOW_MAX, at(1000)), (9, 90));
+    }
 }
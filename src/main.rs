@@ -4,6 +4,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 
 use clap::Parser;
 use tracing::{error, info};
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
 
 use nfs_cachefs::{config, cull, daemon, proto, signals};
 
@@ -20,6 +21,11 @@ struct Args {
     /// Override log level (else read from config / RUST_LOG).
     #[arg(long)]
     log_level: Option<String>,
+
+    /// Print the objects the next cull pass would pick (oldest first) and
+    /// exit. Read-only: does not open /dev/cachefiles.
+    #[arg(long)]
+    cull_dry_run: bool,
 }
 
 static STOP: AtomicBool = AtomicBool::new(false);
@@ -39,10 +45,22 @@ fn main() -> ExitCode {
         }
     };
 
-    init_tracing(
-        args.log_level.as_deref().unwrap_or(&cfg.log.level),
-        &cfg.log.format,
-    );
+    let level = args.log_level.as_deref().unwrap_or(&cfg.log.level);
+    if args.cull_dry_run {
+        // stdout carries the victim list; keep log lines on stderr so the
+        // two never interleave.
+        init_tracing(level, &cfg.log.format, BoxMakeWriter::new(std::io::stderr));
+        return cull_dry_run(&cfg);
+    }
+
+    init_tracing(level, &cfg.log.format, BoxMakeWriter::new(std::io::stdout));
+
+    // ERROR-level span so the field survives any configured log level;
+    // an INFO span would be filtered out (and its field dropped) at warn.
+    let _instance = cfg
+        .instance_name
+        .as_deref()
+        .map(|name| tracing::error_span!("daemon", instance = name).entered());
 
     if let Err(e) = signals::install(handle_signal) {
         error!(error = %e, "failed to install signal handlers");
@@ -85,10 +103,51 @@ fn main() -> ExitCode {
     ExitCode::SUCCESS
 }
 
-fn init_tracing(level: &str, format: &str) {
+fn cull_dry_run(cfg: &config::Config) -> ExitCode {
+    let cache_subdir = cfg.cache_dir.join("cache");
+    // Open it up front so EACCES and ENOENT surface as themselves; the walk
+    // alone cannot tell an unreadable cache from an empty one.
+    if let Err(e) = std::fs::read_dir(&cache_subdir) {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            error!(path = %cache_subdir.display(), "cache subdir does not exist; has the cache ever been bound?");
+        } else {
+            error!(path = %cache_subdir.display(), error = %e, "cannot read cache subdir");
+        }
+        return ExitCode::FAILURE;
+    }
+    let ctx = cull::CullCtx {
+        cache_root: cfg.cache_dir.clone(),
+        batch_size: cfg.cull.batch_size,
+    };
+    let preview = cull::preview(&ctx);
+    let total: u64 = preview.victims.iter().map(|v| v.size).sum();
+    println!("# atime_secs\tsize\tpath");
+    for v in &preview.victims {
+        println!("{}\t{}\t{}", v.atime_secs, v.size, v.path.display());
+    }
+    println!(
+        "# {} objects, {} bytes, {} unreadable entries",
+        preview.victims.len(),
+        total,
+        preview.walk_errors
+    );
+    if preview.walk_errors > 0 {
+        error!(
+            walk_errors = preview.walk_errors,
+            "cache walk was incomplete; the list may miss older objects (--log-level debug shows which entries failed)"
+        );
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn init_tracing(level: &str, format: &str, writer: BoxMakeWriter) {
     use tracing_subscriber::{fmt, EnvFilter};
     let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(level));
-    let builder = fmt().with_env_filter(filter).with_target(false);
+    let builder = fmt()
+        .with_env_filter(filter)
+        .with_target(false)
+        .with_writer(writer);
     match format {
         "json" => {
             builder.json().init();
@@ -1,12 +1,78 @@
 //! Minimal sd_notify(3) client.
 //!
-//! We only need READY=1 after `/dev/cachefiles` is successfully bound. Keeping
-//! this local avoids pulling in libsystemd or an extra crate for one datagram.
+//! We only need READY=1 after `/dev/cachefiles` is successfully bound, plus
+//! WATCHDOG=1 keep-alives when the unit sets `WatchdogSec=`. Keeping this
+//! local avoids pulling in libsystemd or an extra crate for a few datagrams.
 
+use std::cell::Cell;
+use std::ffi::OsStr;
 use std::io;
+use std::time::{Duration, Instant};
+
+use tracing::warn;
 
-#[cfg(target_os = "linux")]
 pub(crate) fn ready(status: &str) -> io::Result<bool> {
+    notify(&format!("READY=1\nSTATUS={status}"))
+}
+
+/// Rate-limited `WATCHDOG=1` sender. [`Watchdog::ping_if_due`] is cheap
+/// enough to call once per walked cache entry, which is how long cull and
+/// graveyard walks keep systemd from mistaking them for a wedged daemon.
+#[derive(Debug)]
+pub(crate) struct Watchdog {
+    interval: Duration,
+    last_ping: Cell<Instant>,
+}
+
+impl Watchdog {
+    /// `Some` only when systemd asked this process for keep-alives.
+    pub(crate) fn from_env() -> Option<Self> {
+        let timeout = watchdog_interval()?;
+        Some(Self {
+            // Per sd_watchdog_enabled(3), ping at half the timeout.
+            interval: timeout / 2,
+            last_ping: Cell::new(Instant::now()),
+        })
+    }
+
+    /// Time between keep-alives.
+    pub(crate) fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    pub(crate) fn ping_if_due(&self) {
+        if self.last_ping.get().elapsed() < self.interval {
+            return;
+        }
+        self.last_ping.set(Instant::now());
+        if let Err(e) = notify("WATCHDOG=1") {
+            warn!(error = %e, "failed to send systemd watchdog keep-alive");
+        }
+    }
+}
+
+/// Watchdog timeout requested by systemd (`WATCHDOG_USEC`), if any and if
+/// it targets this process (`WATCHDOG_PID`, when set, must match our pid).
+fn watchdog_interval() -> Option<Duration> {
+    parse_watchdog_env(
+        std::env::var_os("WATCHDOG_USEC").as_deref(),
+        std::env::var_os("WATCHDOG_PID").as_deref(),
+        std::process::id(),
+    )
+}
+
+fn parse_watchdog_env(usec: Option<&OsStr>, pid: Option<&OsStr>, own_pid: u32) -> Option<Duration> {
+    if let Some(pid) = pid {
+        if pid.to_str()?.parse::<u32>().ok()? != own_pid {
+            return None;
+        }
+    }
+    let usec: u64 = usec?.to_str()?.parse().ok()?;
+    (usec > 0).then(|| Duration::from_micros(usec))
+}
+
+#[cfg(target_os = "linux")]
+fn notify(message: &str) -> io::Result<bool> {
     use std::env;
     use std::os::linux::net::SocketAddrExt;
     use std::os::unix::ffi::OsStrExt;
@@ -27,13 +93,36 @@ pub(crate) fn ready(status: &str) -> io::Result<bool> {
         SocketAddr::from_pathname(Path::new(&socket))?
     };
 
-    let message = format!("READY=1\nSTATUS={status}");
     let sock = UnixDatagram::unbound()?;
     sock.send_to_addr(message.as_bytes(), &addr)?;
     Ok(true)
 }
 
 #[cfg(not(target_os = "linux"))]
-pub(crate) fn ready(_status: &str) -> io::Result<bool> {
+fn notify(_message: &str) -> io::Result<bool> {
     Ok(false)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(usec: Option<&str>, pid: Option<&str>) -> Option<Duration> {
+        parse_watchdog_env(usec.map(OsStr::new), pid.map(OsStr::new), 42)
+    }
+
+    #[test]
+    fn watchdog_env_parsing() {
+        assert_eq!(parse(None, None), None);
+        assert_eq!(parse(Some(""), None), None);
+        assert_eq!(parse(Some("0"), None), None);
+        assert_eq!(parse(Some("soon"), None), None);
+        assert_eq!(parse(Some("30000000"), Some("7")), None);
+        assert_eq!(parse(Some("30000000"), Some("")), None);
+        assert_eq!(parse(Some("30000000"), None), Some(Duration::from_secs(30)));
+        assert_eq!(
+            parse(Some("30000000"), Some("42")),
+            Some(Duration::from_secs(30))
+        );
+    }
+}